@@ -0,0 +1,122 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, MessageInfo, Response, StdResult, Uint128};
+use mars_types::credit_manager::{SmartTokenBalanceResponse, SmartTokenBinding, SmartTokenQueryMsg};
+
+use crate::{
+    error::ContractError,
+    state::{SMART_TOKENS_ENABLED, SMART_TOKEN_DENOMS},
+};
+
+/// Drop-in replacement for `QuerierWrapper::query_balance`: for most denoms this just delegates
+/// to the standard bank query, but for a denom registered as a smart token (while the feature is
+/// enabled), the standard bank query doesn't see it, so the balance is read from the bound
+/// contract's custom query instead.
+pub fn query_balance(deps: Deps, address: &Addr, denom: &str) -> StdResult<Uint128> {
+    if let Some(binding) = smart_token_binding(deps, denom)? {
+        return query_smart_token_balance(deps, &binding, address, denom);
+    }
+
+    Ok(deps.querier.query_balance(address, denom)?.amount)
+}
+
+fn smart_token_binding(deps: Deps, denom: &str) -> StdResult<Option<SmartTokenBinding>> {
+    if !SMART_TOKENS_ENABLED.may_load(deps.storage)?.unwrap_or(false) {
+        return Ok(None);
+    }
+    SMART_TOKEN_DENOMS.may_load(deps.storage, denom)
+}
+
+fn query_smart_token_balance(
+    deps: Deps,
+    binding: &SmartTokenBinding,
+    address: &Addr,
+    denom: &str,
+) -> StdResult<Uint128> {
+    let res: SmartTokenBalanceResponse = deps.querier.query_wasm_smart(
+        binding.query_contract.clone(),
+        &SmartTokenQueryMsg::Balance {
+            address: address.to_string(),
+            denom: denom.to_string(),
+        },
+    )?;
+    Ok(res.amount)
+}
+
+/// Owner-only: turn the smart-token balance path on or off. Off by default so existing Osmosis
+/// deployments, which have no registered bindings, are unaffected either way.
+pub fn set_smart_tokens_enabled(
+    deps: DepsMut,
+    info: MessageInfo,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    SMART_TOKENS_ENABLED.save(deps.storage, &enabled)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_smart_tokens_enabled")
+        .add_attribute("enabled", enabled.to_string()))
+}
+
+/// Owner-only: register `denom` as a smart token whose balance is resolved via `query_contract`.
+pub fn set_smart_token_binding(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    query_contract: Addr,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    SMART_TOKEN_DENOMS.save(deps.storage, &denom, &SmartTokenBinding {
+        query_contract: query_contract.clone(),
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_smart_token_binding")
+        .add_attribute("denom", denom)
+        .add_attribute("query_contract", query_contract))
+}
+
+/// Owner-only: reverse `set_smart_token_binding`, falling `denom` back to the standard bank query.
+pub fn remove_smart_token_binding(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    SMART_TOKEN_DENOMS.remove(deps.storage, &denom);
+
+    Ok(Response::new().add_attribute("action", "remove_smart_token_binding").add_attribute("denom", denom))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_info};
+
+    use super::*;
+
+    #[test]
+    fn smart_tokens_disabled_by_default() {
+        let deps = mock_dependencies();
+        assert!(!SMART_TOKENS_ENABLED.may_load(&deps.storage).unwrap().unwrap_or(false));
+    }
+
+    #[test]
+    fn owner_can_enable_and_register_binding() {
+        let mut deps = mock_dependencies();
+        cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some("owner")).unwrap();
+
+        set_smart_tokens_enabled(deps.as_mut(), mock_info("owner", &[]), true).unwrap();
+        set_smart_token_binding(
+            deps.as_mut(),
+            mock_info("owner", &[]),
+            "factory/contract/utoken".to_string(),
+            Addr::unchecked("registry_contract"),
+        )
+        .unwrap();
+
+        let binding =
+            SMART_TOKEN_DENOMS.load(&deps.storage, "factory/contract/utoken").unwrap();
+        assert_eq!(binding.query_contract, Addr::unchecked("registry_contract"));
+    }
+}
@@ -0,0 +1,153 @@
+use cosmwasm_std::{DepsMut, MessageInfo, Response};
+use mars_types::credit_manager::CmEmergencyUpdate;
+
+use crate::{
+    error::ContractError,
+    state::{BORROWING_DISABLED, COIN_DISALLOWED, SWAP_DISABLED},
+};
+
+/// Dispatch an emergency-owner power. Owner-only.
+pub fn dispatch_emergency_update(
+    deps: DepsMut,
+    info: MessageInfo,
+    update: CmEmergencyUpdate,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let response = match update {
+        CmEmergencyUpdate::DisableBorrowing(denom) => {
+            BORROWING_DISABLED.save(deps.storage, &denom, &true)?;
+            Response::new().add_attribute("action", "disable_borrowing").add_attribute("denom", denom)
+        }
+        CmEmergencyUpdate::DisallowCoin(denom) => {
+            COIN_DISALLOWED.save(deps.storage, &denom, &true)?;
+            Response::new().add_attribute("action", "disallow_coin").add_attribute("denom", denom)
+        }
+        CmEmergencyUpdate::DisableSwapping(denom) => {
+            SWAP_DISABLED.save(deps.storage, &denom, &true)?;
+            Response::new().add_attribute("action", "disable_swapping").add_attribute("denom", denom)
+        }
+        CmEmergencyUpdate::EnableSwapping(denom) => {
+            SWAP_DISABLED.remove(deps.storage, &denom);
+            Response::new().add_attribute("action", "enable_swapping").add_attribute("denom", denom)
+        }
+    };
+
+    Ok(response)
+}
+
+pub fn assert_swapping_enabled(deps: cosmwasm_std::Deps, denom: &str) -> Result<(), ContractError> {
+    if SWAP_DISABLED.may_load(deps.storage, denom)?.unwrap_or(false) {
+        return Err(ContractError::SwappingDisabled {
+            denom: denom.to_string(),
+        });
+    }
+    Ok(())
+}
+
+pub fn assert_borrowing_enabled(deps: cosmwasm_std::Deps, denom: &str) -> Result<(), ContractError> {
+    if BORROWING_DISABLED.may_load(deps.storage, denom)?.unwrap_or(false) {
+        return Err(ContractError::BorrowingDisabled {
+            denom: denom.to_string(),
+        });
+    }
+    Ok(())
+}
+
+pub fn assert_coin_allowed(deps: cosmwasm_std::Deps, denom: &str) -> Result<(), ContractError> {
+    if COIN_DISALLOWED.may_load(deps.storage, denom)?.unwrap_or(false) {
+        return Err(ContractError::CoinDisallowed {
+            denom: denom.to_string(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_info};
+
+    use super::*;
+
+    #[test]
+    fn swapping_enabled_by_default() {
+        let deps = mock_dependencies();
+        assert_swapping_enabled(deps.as_ref(), "uosmo").unwrap();
+    }
+
+    #[test]
+    fn owner_can_disable_and_enable_swapping() {
+        let mut deps = mock_dependencies();
+        cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some("owner")).unwrap();
+
+        dispatch_emergency_update(
+            deps.as_mut(),
+            mock_info("owner", &[]),
+            CmEmergencyUpdate::DisableSwapping("uosmo".to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            assert_swapping_enabled(deps.as_ref(), "uosmo"),
+            Err(ContractError::SwappingDisabled {
+                denom: "uosmo".to_string(),
+            })
+        );
+
+        dispatch_emergency_update(
+            deps.as_mut(),
+            mock_info("owner", &[]),
+            CmEmergencyUpdate::EnableSwapping("uosmo".to_string()),
+        )
+        .unwrap();
+
+        assert_swapping_enabled(deps.as_ref(), "uosmo").unwrap();
+    }
+
+    #[test]
+    fn owner_can_disable_borrowing_and_disallow_coin() {
+        let mut deps = mock_dependencies();
+        cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some("owner")).unwrap();
+
+        dispatch_emergency_update(
+            deps.as_mut(),
+            mock_info("owner", &[]),
+            CmEmergencyUpdate::DisableBorrowing("uatom".to_string()),
+        )
+        .unwrap();
+        assert_eq!(
+            assert_borrowing_enabled(deps.as_ref(), "uatom"),
+            Err(ContractError::BorrowingDisabled {
+                denom: "uatom".to_string(),
+            })
+        );
+
+        dispatch_emergency_update(
+            deps.as_mut(),
+            mock_info("owner", &[]),
+            CmEmergencyUpdate::DisallowCoin("uatom".to_string()),
+        )
+        .unwrap();
+        assert_eq!(
+            assert_coin_allowed(deps.as_ref(), "uatom"),
+            Err(ContractError::CoinDisallowed {
+                denom: "uatom".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn non_owner_cannot_dispatch_emergency_update() {
+        let mut deps = mock_dependencies();
+        cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some("owner")).unwrap();
+
+        let err = dispatch_emergency_update(
+            deps.as_mut(),
+            mock_info("not_the_owner", &[]),
+            CmEmergencyUpdate::DisableSwapping("uosmo".to_string()),
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, ContractError::Ownership(_)));
+    }
+}
@@ -0,0 +1,60 @@
+use cosmwasm_std::{Decimal, OverflowError, StdError};
+use cw_ownable::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+
+    #[error("{user} is not the owner of {account_id}")]
+    NotTokenOwner {
+        user: String,
+        account_id: String,
+    },
+
+    #[error("{0} is not whitelisted")]
+    NotWhitelisted(String),
+
+    #[error("Amount cannot be zero")]
+    NoAmount,
+
+    #[error("Slippage {slippage} exceeds max allowed slippage of {max_slippage}")]
+    SlippageExceeded {
+        slippage: Decimal,
+        max_slippage: Decimal,
+    },
+
+    #[error("Swapping {denom} is currently disabled")]
+    SwappingDisabled {
+        denom: String,
+    },
+
+    #[error("Borrowing {denom} is currently disabled")]
+    BorrowingDisabled {
+        denom: String,
+    },
+
+    #[error("{denom} is currently disallowed as collateral")]
+    CoinDisallowed {
+        denom: String,
+    },
+
+    #[error("Swap of {denom} exceeds rate limit: attempted {attempted}, limit {limit}")]
+    RateLimitExceeded {
+        denom: String,
+        limit: cosmwasm_std::Uint128,
+        attempted: cosmwasm_std::Uint128,
+    },
+
+    #[error("Channel {channel_id} is not allowed for IBC transfers")]
+    ChannelNotAllowed {
+        channel_id: String,
+    },
+}
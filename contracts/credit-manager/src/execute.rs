@@ -0,0 +1,40 @@
+use cosmwasm_std::{DepsMut, Env, Response};
+use mars_types::credit_manager::Action;
+
+use crate::{error::ContractError, ibc, swap};
+
+/// Dispatch a single `Action` taken against `account_id`. Token-ownership and other
+/// account-level checks are performed by the caller before actions are dispatched.
+pub fn dispatch_action(
+    deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    action: Action,
+) -> Result<Response, ContractError> {
+    match action {
+        Action::Deposit(_) | Action::Withdraw(_) => {
+            // handled elsewhere; not part of this module
+            Ok(Response::new())
+        }
+        Action::SwapExactIn {
+            coin_in,
+            denom_out,
+            slippage,
+        } => swap::swap_exact_in(deps, env, account_id, &coin_in, &denom_out, slippage),
+        Action::SwapExactOut {
+            denom_in,
+            coin_out,
+            slippage,
+        } => swap::swap_exact_out(deps, env, account_id, &denom_in, &coin_out, slippage),
+        Action::SwapAndTransfer {
+            coin_in,
+            denom_out,
+            slippage,
+            channel_id,
+            to_address,
+            timeout,
+        } => ibc::swap_and_transfer(
+            deps, env, account_id, &coin_in, denom_out, slippage, channel_id, to_address, timeout,
+        ),
+    }
+}
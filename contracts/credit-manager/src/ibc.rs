@@ -0,0 +1,177 @@
+use cosmwasm_std::{
+    Coin, Decimal, DepsMut, Env, Event, IbcMsg, IbcTimeout, MessageInfo, Reply, Response, StdError,
+    SubMsg, SubMsgResult,
+};
+use cw_storage_plus::Item;
+use mars_types::credit_manager::ActionCoin;
+use serde::{Deserialize, Serialize};
+
+use crate::{error::ContractError, state::ALLOWED_CHANNELS, swap};
+
+/// Reply id used to capture the swap leg of `Action::SwapAndTransfer` before issuing the
+/// follow-on IBC transfer
+pub const SWAP_AND_TRANSFER_REPLY_ID: u64 = 10_001;
+
+/// The swapped-out amount isn't known until the swap sub-message executes, so we snapshot our
+/// own balance of `denom_out` beforehand and diff it against the post-swap balance in the reply.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PendingIbcTransfer {
+    pub balance_before: cosmwasm_std::Uint128,
+    pub denom_out: String,
+    pub channel_id: String,
+    pub to_address: String,
+    pub timeout: IbcTimeout,
+}
+
+pub const PENDING_IBC_TRANSFER: Item<PendingIbcTransfer> = Item::new("pending_ibc_transfer");
+
+pub fn assert_channel_allowed(deps: cosmwasm_std::Deps, channel_id: &str) -> Result<(), ContractError> {
+    if !ALLOWED_CHANNELS.may_load(deps.storage, channel_id)?.unwrap_or(false) {
+        return Err(ContractError::ChannelNotAllowed {
+            channel_id: channel_id.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Owner-only: allow/disallow an IBC channel for `Action::SwapAndTransfer`
+pub fn set_channel_allowed(
+    deps: DepsMut,
+    info: MessageInfo,
+    channel_id: String,
+    allowed: bool,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    if allowed {
+        ALLOWED_CHANNELS.save(deps.storage, &channel_id, &true)?;
+    } else {
+        ALLOWED_CHANNELS.remove(deps.storage, &channel_id);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_channel_allowed")
+        .add_attribute("channel_id", channel_id)
+        .add_attribute("allowed", allowed.to_string()))
+}
+
+/// Swap `coin_in` for `denom_out` (reusing all of `swap_exact_in`'s validation), then bridge the
+/// proceeds to `to_address` over `channel_id` once the swap sub-message completes.
+pub fn swap_and_transfer(
+    mut deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    coin_in: &ActionCoin,
+    denom_out: String,
+    slippage: Decimal,
+    channel_id: String,
+    to_address: String,
+    timeout: IbcTimeout,
+) -> Result<Response, ContractError> {
+    assert_channel_allowed(deps.as_ref(), &channel_id)?;
+
+    let swap_response = swap::swap_exact_in(
+        deps.branch(),
+        env.clone(),
+        account_id,
+        coin_in,
+        &denom_out,
+        slippage,
+    )?;
+
+    let Some(swap_msg) = swap_response.messages.into_iter().next() else {
+        // the swap was a no-op (empty full-balance swap); nothing to bridge
+        return Ok(swap_response);
+    };
+
+    let balance_before = crate::balance::query_balance(deps.as_ref(), &env.contract.address, &denom_out)?;
+    PENDING_IBC_TRANSFER.save(
+        deps.storage,
+        &PendingIbcTransfer {
+            balance_before,
+            denom_out,
+            channel_id,
+            to_address,
+            timeout,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(swap_msg.msg, SWAP_AND_TRANSFER_REPLY_ID))
+        .add_attributes(swap_response.attributes)
+        .add_attribute("action", "swap_and_transfer"))
+}
+
+pub fn handle_swap_and_transfer_reply(
+    deps: DepsMut,
+    env: Env,
+    reply: Reply,
+) -> Result<Response, ContractError> {
+    // the swap sub-message already returned success by the time we get here
+    let SubMsgResult::Ok(_) = reply.result else {
+        return Err(ContractError::Std(StdError::generic_err("swap sub-message failed")));
+    };
+
+    let pending = PENDING_IBC_TRANSFER.load(deps.storage)?;
+    PENDING_IBC_TRANSFER.remove(deps.storage);
+
+    let balance_after =
+        crate::balance::query_balance(deps.as_ref(), &env.contract.address, &pending.denom_out)?;
+    let amount_out = balance_after.checked_sub(pending.balance_before).map_err(cosmwasm_std::StdError::overflow)?;
+
+    let transfer_msg = IbcMsg::Transfer {
+        channel_id: pending.channel_id,
+        to_address: pending.to_address,
+        amount: Coin {
+            denom: pending.denom_out,
+            amount: amount_out,
+        },
+        timeout: pending.timeout,
+    };
+
+    Ok(Response::new().add_message(transfer_msg).add_event(
+        Event::new("swap_and_transfer_completed").add_attribute("amount_out", amount_out.to_string()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_info};
+
+    use super::*;
+
+    #[test]
+    fn channel_not_allowed_by_default() {
+        let deps = mock_dependencies();
+        assert_eq!(
+            assert_channel_allowed(deps.as_ref(), "channel-0"),
+            Err(ContractError::ChannelNotAllowed {
+                channel_id: "channel-0".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn owner_can_allow_and_disallow_channel() {
+        let mut deps = mock_dependencies();
+        cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some("owner")).unwrap();
+
+        set_channel_allowed(
+            deps.as_mut(),
+            mock_info("owner", &[]),
+            "channel-0".to_string(),
+            true,
+        )
+        .unwrap();
+        assert_channel_allowed(deps.as_ref(), "channel-0").unwrap();
+
+        set_channel_allowed(
+            deps.as_mut(),
+            mock_info("owner", &[]),
+            "channel-0".to_string(),
+            false,
+        )
+        .unwrap();
+        assert!(assert_channel_allowed(deps.as_ref(), "channel-0").is_err());
+    }
+}
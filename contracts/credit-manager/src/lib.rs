@@ -0,0 +1,8 @@
+pub mod balance;
+pub mod emergency;
+pub mod error;
+pub mod execute;
+pub mod ibc;
+pub mod limiter;
+pub mod state;
+pub mod swap;
@@ -0,0 +1,172 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Uint128;
+
+/// A single time bucket within a `WindowLimiter`'s rolling window
+#[cw_serde]
+pub struct Division {
+    /// Unix timestamp (seconds) this division started accumulating at
+    pub started_at: u64,
+    /// Cumulative amount swapped out of the limited denom during this division
+    pub amount: Uint128,
+}
+
+/// Caps the total amount of a denom that can be swapped out over a rolling `window_size`,
+/// approximated by `division_count` fixed-size buckets. Divisions that have fully aged out of
+/// the window are pruned lazily, on write, so gas cost stays bounded regardless of how long a
+/// denom has been tracked.
+#[cw_serde]
+pub struct WindowConfig {
+    /// Length of the rolling window, in seconds (e.g. 86_400 for one day)
+    pub window_size: u64,
+    /// Number of buckets the window is split into; more buckets smooth the limit at the cost of
+    /// more storage per denom
+    pub division_count: u64,
+    /// Maximum cumulative outflow allowed across the window
+    pub net_limit: Uint128,
+}
+
+impl WindowConfig {
+    fn division_size(&self) -> u64 {
+        (self.window_size / self.division_count.max(1)).max(1)
+    }
+}
+
+#[cw_serde]
+pub struct WindowLimiter {
+    pub config: WindowConfig,
+    pub divisions: Vec<Division>,
+}
+
+impl WindowLimiter {
+    pub fn new(config: WindowConfig) -> Self {
+        Self {
+            config,
+            divisions: vec![],
+        }
+    }
+
+    fn prune(&mut self, now: u64) {
+        let window_size = self.config.window_size;
+        self.divisions.retain(|d| now.saturating_sub(d.started_at) < window_size);
+    }
+
+    fn moving_sum(&self) -> Uint128 {
+        self.divisions.iter().map(|d| d.amount).sum()
+    }
+}
+
+/// A limiter applied to outflows of a single denom. Registered and deregistered by the owner;
+/// multiple limiters may apply to the same denom (e.g. a `Static` cap alongside a `Window` cap).
+#[cw_serde]
+pub enum Limiter {
+    /// Caps cumulative outflow over a rolling window
+    Window(WindowLimiter),
+    /// Caps the size of a single swap
+    Static(Uint128),
+}
+
+impl Limiter {
+    /// Expire stale divisions, check whether `amount` would push this limiter over its limit,
+    /// and if not, record `amount` against it. Returns `(limit, attempted)` on rejection.
+    pub fn check_and_update(&mut self, now: u64, amount: Uint128) -> Result<(), (Uint128, Uint128)> {
+        match self {
+            Limiter::Window(limiter) => {
+                limiter.prune(now);
+
+                let attempted = limiter
+                    .moving_sum()
+                    .checked_add(amount)
+                    .map_err(|_| (limiter.config.net_limit, Uint128::MAX))?;
+                if attempted > limiter.config.net_limit {
+                    return Err((limiter.config.net_limit, attempted));
+                }
+
+                let division_size = limiter.config.division_size();
+                match limiter.divisions.last_mut() {
+                    Some(d) if now.saturating_sub(d.started_at) < division_size => {
+                        d.amount = d
+                            .amount
+                            .checked_add(amount)
+                            .map_err(|_| (limiter.config.net_limit, Uint128::MAX))?;
+                    }
+                    _ => limiter.divisions.push(Division {
+                        started_at: now,
+                        amount,
+                    }),
+                }
+
+                Ok(())
+            }
+            Limiter::Static(limit) => {
+                if amount > *limit {
+                    return Err((*limit, amount));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn static_limiter_allows_up_to_limit() {
+        let mut limiter = Limiter::Static(Uint128::new(100));
+        assert_eq!(limiter.check_and_update(0, Uint128::new(100)), Ok(()));
+    }
+
+    #[test]
+    fn static_limiter_rejects_over_limit() {
+        let mut limiter = Limiter::Static(Uint128::new(100));
+        assert_eq!(
+            limiter.check_and_update(0, Uint128::new(101)),
+            Err((Uint128::new(100), Uint128::new(101)))
+        );
+    }
+
+    #[test]
+    fn window_limiter_rejects_once_net_limit_reached() {
+        let mut limiter = Limiter::Window(WindowLimiter::new(WindowConfig {
+            window_size: 86_400,
+            division_count: 24,
+            net_limit: Uint128::new(1_000),
+        }));
+
+        assert_eq!(limiter.check_and_update(0, Uint128::new(600)), Ok(()));
+        assert_eq!(limiter.check_and_update(3_600, Uint128::new(400)), Ok(()));
+        assert_eq!(
+            limiter.check_and_update(7_200, Uint128::new(1)),
+            Err((Uint128::new(1_000), Uint128::new(1_001)))
+        );
+    }
+
+    #[test]
+    fn window_limiter_expires_old_divisions() {
+        let mut limiter = Limiter::Window(WindowLimiter::new(WindowConfig {
+            window_size: 86_400,
+            division_count: 24,
+            net_limit: Uint128::new(1_000),
+        }));
+
+        assert_eq!(limiter.check_and_update(0, Uint128::new(1_000)), Ok(()));
+        // a day later the original division has fully expired, so the full limit is available again
+        assert_eq!(limiter.check_and_update(86_401, Uint128::new(1_000)), Ok(()));
+    }
+
+    #[test]
+    fn window_limiter_rejects_rather_than_overflowing_on_moving_sum() {
+        let mut limiter = Limiter::Window(WindowLimiter::new(WindowConfig {
+            window_size: 86_400,
+            division_count: 24,
+            net_limit: Uint128::MAX,
+        }));
+
+        assert_eq!(limiter.check_and_update(0, Uint128::MAX), Ok(()));
+        assert_eq!(
+            limiter.check_and_update(1, Uint128::new(1)),
+            Err((Uint128::MAX, Uint128::MAX))
+        );
+    }
+}
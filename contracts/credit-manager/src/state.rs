@@ -0,0 +1,46 @@
+use cosmwasm_std::{Addr, Decimal};
+use cw_storage_plus::{Item, Map};
+use mars_types::credit_manager::SmartTokenBinding;
+
+use crate::limiter::Limiter;
+
+/// Address of the swapper contract used to execute `SwapExactIn`/`SwapExactOut`
+pub const SWAPPER: Item<Addr> = Item::new("swapper");
+
+/// Address of the params contract, source of truth for which denoms are whitelisted as collateral
+pub const PARAMS: Item<Addr> = Item::new("params");
+
+/// Maximum slippage allowed on any swap, set by governance
+pub const MAX_SLIPPAGE: Item<Decimal> = Item::new("max_slippage");
+
+/// Per-account, per-denom coin balances held by this contract on behalf of credit accounts
+pub const COIN_BALANCES: Map<(&str, &str), cosmwasm_std::Uint128> = Map::new("coin_balances");
+
+/// Rate limiters registered against outflows of a given denom. A denom may have zero or more
+/// limiters (e.g. a `Static` per-swap cap alongside a `Window` rolling cap); all must pass for a
+/// swap to proceed.
+pub const SWAP_RATE_LIMITS: Map<&str, Vec<Limiter>> = Map::new("swap_rate_limits");
+
+/// Denoms the emergency owner has instantly frozen out of `SwapExactIn`/`SwapExactOut`. Absence
+/// from this map (the common case) means swapping is enabled.
+pub const SWAP_DISABLED: Map<&str, bool> = Map::new("swap_disabled");
+
+/// Denoms the emergency owner has instantly frozen out of borrowing. Absence from this map (the
+/// common case) means borrowing is enabled.
+pub const BORROWING_DISABLED: Map<&str, bool> = Map::new("borrowing_disabled");
+
+/// Denoms the emergency owner has instantly disallowed as new collateral. Absence from this map
+/// (the common case) means the denom may still be deposited as collateral.
+pub const COIN_DISALLOWED: Map<&str, bool> = Map::new("coin_disallowed");
+
+/// IBC channels governance has allowlisted for `Action::SwapAndTransfer`. Absence from this map
+/// means the channel is not allowed.
+pub const ALLOWED_CHANNELS: Map<&str, bool> = Map::new("allowed_channels");
+
+/// Feature flag gating the smart-token balance path. Off by default so existing Osmosis
+/// deployments, which have no need for it, are unaffected.
+pub const SMART_TOKENS_ENABLED: Item<bool> = Item::new("smart_tokens_enabled");
+
+/// Denoms whose balances must be resolved via a custom query rather than the standard bank
+/// query, and where to send that query. See `crate::balance`.
+pub const SMART_TOKEN_DENOMS: Map<&str, SmartTokenBinding> = Map::new("smart_token_denoms");
@@ -0,0 +1,403 @@
+use cosmwasm_std::{
+    Coin, Decimal, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult, SubMsg,
+    SubMsgResult, Uint128, WasmMsg,
+};
+use cw_storage_plus::Item;
+use mars_types::{
+    credit_manager::{ActionAmount, ActionCoin},
+    swapper::{
+        EstimateExactOutSwapResponse, ExecuteMsg as SwapperExecuteMsg, QueryMsg as SwapperQueryMsg,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    balance,
+    error::ContractError,
+    limiter::Limiter,
+    state::{COIN_BALANCES, MAX_SLIPPAGE, PARAMS, SWAP_RATE_LIMITS},
+};
+
+/// Reply id used to capture `swap_exact_in`'s swapped-out amount before crediting it to the
+/// account's ledger.
+pub const SWAP_EXACT_IN_REPLY_ID: u64 = 10_002;
+
+/// Reply id used to capture `swap_exact_out`'s actual spend (and any unspent refund) before
+/// updating the account's ledger.
+pub const SWAP_EXACT_OUT_REPLY_ID: u64 = 10_003;
+
+/// The swapped-out amount isn't known until the swap sub-message executes, so we snapshot this
+/// contract's own balance of `denom_out` beforehand and diff it against the post-swap balance in
+/// the reply, the same way `ibc::swap_and_transfer` does.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PendingSwapExactIn {
+    pub account_id: String,
+    pub denom_out: String,
+    pub balance_before: Uint128,
+}
+
+pub const PENDING_SWAP_EXACT_IN: Item<PendingSwapExactIn> = Item::new("pending_swap_exact_in");
+
+/// Snapshot of both legs' balances before a `swap_exact_out` sub-message executes, so the reply
+/// can credit the actual `denom_out` received and refund whatever of the `denom_in` reservation
+/// went unspent.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PendingSwapExactOut {
+    pub account_id: String,
+    pub denom_in: String,
+    pub denom_out: String,
+    /// The amount of `denom_in` reserved (decremented from the ledger and sent as funds) up
+    /// front; the difference between this and what the swap sub-message actually spends is what
+    /// gets refunded to the ledger in the reply.
+    pub reserved_amount: Uint128,
+    pub balance_before_in: Uint128,
+    pub balance_before_out: Uint128,
+}
+
+pub const PENDING_SWAP_EXACT_OUT: Item<PendingSwapExactOut> = Item::new("pending_swap_exact_out");
+
+/// Resolve an `ActionCoin` (which may request the account's full balance) into a concrete
+/// `Coin`, reading the current balance held on behalf of `account_id`.
+pub fn resolve_action_coin(deps: Deps, account_id: &str, coin: &ActionCoin) -> StdResult<Coin> {
+    let amount = match coin.amount {
+        ActionAmount::Exact(amount) => amount,
+        ActionAmount::AccountBalance => {
+            COIN_BALANCES.may_load(deps.storage, (account_id, &coin.denom))?.unwrap_or_default()
+        }
+    };
+
+    Ok(Coin {
+        denom: coin.denom.clone(),
+        amount,
+    })
+}
+
+fn assert_denom_whitelisted(deps: Deps, denom: &str) -> Result<(), ContractError> {
+    let params_addr = PARAMS.load(deps.storage)?;
+    let asset_params: Option<mars_types::params::AssetParams> = deps
+        .querier
+        .query_wasm_smart(params_addr, &mars_types::params::QueryMsg::AssetParams {
+            denom: denom.to_string(),
+        })?;
+
+    let is_whitelisted =
+        asset_params.map(|p| p.credit_manager.whitelisted).unwrap_or(false);
+    if !is_whitelisted {
+        return Err(ContractError::NotWhitelisted(denom.to_string()));
+    }
+    Ok(())
+}
+
+fn assert_max_slippage(deps: Deps, slippage: Decimal) -> Result<(), ContractError> {
+    let max_slippage = MAX_SLIPPAGE.load(deps.storage)?;
+    if slippage > max_slippage {
+        return Err(ContractError::SlippageExceeded {
+            slippage,
+            max_slippage,
+        });
+    }
+    Ok(())
+}
+
+/// Check `denom`'s registered rate limiters (if any) against this swap's `amount`, and record
+/// the outflow against each one that passes. A denom with no registered limiters is unrestricted.
+fn assert_rate_limit(deps: DepsMut, env: &Env, denom: &str, amount: Uint128) -> Result<(), ContractError> {
+    let Some(mut limiters) = SWAP_RATE_LIMITS.may_load(deps.storage, denom)? else {
+        return Ok(());
+    };
+
+    let now = env.block.time.seconds();
+    for limiter in limiters.iter_mut() {
+        limiter.check_and_update(now, amount).map_err(|(limit, attempted)| {
+            ContractError::RateLimitExceeded {
+                denom: denom.to_string(),
+                limit,
+                attempted,
+            }
+        })?;
+    }
+
+    SWAP_RATE_LIMITS.save(deps.storage, denom, &limiters)?;
+    Ok(())
+}
+
+/// Register a new rate limiter for `denom`. Owner-only; a denom may accumulate several
+/// limiters, all of which must pass for an outflow to proceed.
+pub fn register_rate_limiter(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    limiter: Limiter,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    SWAP_RATE_LIMITS.update(deps.storage, &denom, |opt| -> StdResult<_> {
+        let mut limiters = opt.unwrap_or_default();
+        limiters.push(limiter);
+        Ok(limiters)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_swap_rate_limiter")
+        .add_attribute("denom", denom))
+}
+
+/// Remove all rate limiters registered for `denom`. Owner-only.
+pub fn deregister_rate_limiters(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    SWAP_RATE_LIMITS.remove(deps.storage, &denom);
+
+    Ok(Response::new()
+        .add_attribute("action", "deregister_swap_rate_limiters")
+        .add_attribute("denom", denom))
+}
+
+fn decrement_coin_balance(
+    deps: DepsMut,
+    account_id: &str,
+    coin: &Coin,
+) -> Result<(), ContractError> {
+    COIN_BALANCES.update(deps.storage, (account_id, &coin.denom), |opt| -> Result<_, ContractError> {
+        let balance = opt.unwrap_or_default();
+        Ok(balance.checked_sub(coin.amount)?)
+    })?;
+    Ok(())
+}
+
+fn increment_coin_balance(
+    deps: DepsMut,
+    account_id: &str,
+    coin: &Coin,
+) -> Result<(), ContractError> {
+    COIN_BALANCES.update(deps.storage, (account_id, &coin.denom), |opt| -> StdResult<_> {
+        Ok(opt.unwrap_or_default().checked_add(coin.amount)?)
+    })?;
+    Ok(())
+}
+
+/// Estimate the amount of `denom_in` the swapper will need to produce exactly `coin_out`, used to
+/// cap how much of `denom_in` a `swap_exact_out` reserves up front.
+fn estimate_exact_out_amount_in(
+    deps: Deps,
+    denom_in: &str,
+    coin_out: &Coin,
+) -> Result<Uint128, ContractError> {
+    let swapper = crate::state::SWAPPER.load(deps.storage)?;
+    let estimate: EstimateExactOutSwapResponse = deps.querier.query_wasm_smart(
+        swapper,
+        &SwapperQueryMsg::EstimateExactOutSwap {
+            coin_out: coin_out.clone(),
+            denom_in: denom_in.to_string(),
+        },
+    )?;
+    Ok(estimate.amount)
+}
+
+pub fn swap_exact_in(
+    mut deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    coin_in: &ActionCoin,
+    denom_out: &str,
+    slippage: Decimal,
+) -> Result<Response, ContractError> {
+    let is_full_balance = matches!(coin_in.amount, ActionAmount::AccountBalance);
+    let coin_in = resolve_action_coin(deps.as_ref(), account_id, coin_in)?;
+    if coin_in.amount.is_zero() {
+        // a "swap whatever is left" action is a safe no-op when there's nothing left, which
+        // makes it safe to tack onto the end of a multi-action sequence without knowing the
+        // exact balance ahead of time. An explicit `Exact(0)` is still a user error.
+        if is_full_balance {
+            return Ok(Response::new()
+                .add_attribute("action", "swap_exact_in")
+                .add_attribute("account_id", account_id));
+        }
+        return Err(ContractError::NoAmount);
+    }
+
+    crate::emergency::assert_swapping_enabled(deps.as_ref(), &coin_in.denom)?;
+    crate::emergency::assert_swapping_enabled(deps.as_ref(), denom_out)?;
+    assert_denom_whitelisted(deps.as_ref(), denom_out)?;
+    assert_max_slippage(deps.as_ref(), slippage)?;
+    assert_rate_limit(deps.branch(), &env, &coin_in.denom, coin_in.amount)?;
+
+    decrement_coin_balance(deps.branch(), account_id, &coin_in)?;
+
+    let swapper = crate::state::SWAPPER.load(deps.storage)?;
+    let swap_msg = WasmMsg::Execute {
+        contract_addr: swapper.to_string(),
+        msg: cosmwasm_std::to_json_binary(&SwapperExecuteMsg::<cosmwasm_std::Empty>::SwapExactIn {
+            coin_in: coin_in.clone(),
+            denom_out: denom_out.to_string(),
+            slippage,
+        })?,
+        funds: vec![coin_in.clone()],
+    };
+
+    // the swapped-out amount isn't known until the swap sub-message lands; snapshot our own
+    // balance now and diff it against the post-swap balance in the reply
+    let balance_before = balance::query_balance(deps.as_ref(), &env.contract.address, denom_out)?;
+    PENDING_SWAP_EXACT_IN.save(
+        deps.storage,
+        &PendingSwapExactIn {
+            account_id: account_id.to_string(),
+            denom_out: denom_out.to_string(),
+            balance_before,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(swap_msg, SWAP_EXACT_IN_REPLY_ID))
+        .add_attribute("action", "swap_exact_in")
+        .add_attribute("account_id", account_id))
+}
+
+/// Swap the minimum amount of `denom_in` necessary to acquire exactly `coin_out`, capping the
+/// spend at `estimated_amount_in / (1 - slippage)` and crediting back whatever of that
+/// reservation the swapper contract doesn't actually spend.
+pub fn swap_exact_out(
+    mut deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    denom_in: &str,
+    coin_out: &ActionCoin,
+    slippage: Decimal,
+) -> Result<Response, ContractError> {
+    let coin_out = resolve_action_coin(deps.as_ref(), account_id, coin_out)?;
+    if coin_out.amount.is_zero() {
+        return Err(ContractError::NoAmount);
+    }
+
+    crate::emergency::assert_swapping_enabled(deps.as_ref(), denom_in)?;
+    crate::emergency::assert_swapping_enabled(deps.as_ref(), &coin_out.denom)?;
+    assert_denom_whitelisted(deps.as_ref(), &coin_out.denom)?;
+    assert_max_slippage(deps.as_ref(), slippage)?;
+
+    // cap the reserved spend at the swapper's own price estimate / (1 - slippage); the swapper
+    // contract refunds whatever of this reservation is not actually spent, which gets credited
+    // back in the reply below
+    let estimated_amount_in = estimate_exact_out_amount_in(deps.as_ref(), denom_in, &coin_out)?;
+    let max_spend = estimated_amount_in.checked_div_ceil(Decimal::one() - slippage).map_err(|e| {
+        cosmwasm_std::StdError::generic_err(e.to_string())
+    })?;
+    let coin_in_reserved = Coin {
+        denom: denom_in.to_string(),
+        amount: max_spend,
+    };
+
+    assert_rate_limit(deps.branch(), &env, denom_in, coin_in_reserved.amount)?;
+    decrement_coin_balance(deps.branch(), account_id, &coin_in_reserved)?;
+
+    let swapper = crate::state::SWAPPER.load(deps.storage)?;
+    let swap_msg = WasmMsg::Execute {
+        contract_addr: swapper.to_string(),
+        msg: cosmwasm_std::to_json_binary(&SwapperExecuteMsg::<cosmwasm_std::Empty>::SwapExactOut {
+            coin_in: coin_in_reserved.clone(),
+            coin_out: coin_out.clone(),
+            slippage,
+        })?,
+        funds: vec![coin_in_reserved.clone()],
+    };
+
+    let balance_before_in =
+        balance::query_balance(deps.as_ref(), &env.contract.address, denom_in)?;
+    let balance_before_out =
+        balance::query_balance(deps.as_ref(), &env.contract.address, &coin_out.denom)?;
+    PENDING_SWAP_EXACT_OUT.save(
+        deps.storage,
+        &PendingSwapExactOut {
+            account_id: account_id.to_string(),
+            denom_in: denom_in.to_string(),
+            denom_out: coin_out.denom.clone(),
+            reserved_amount: max_spend,
+            balance_before_in,
+            balance_before_out,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(swap_msg, SWAP_EXACT_OUT_REPLY_ID))
+        .add_attribute("action", "swap_exact_out")
+        .add_attribute("account_id", account_id))
+}
+
+/// Credit `swap_exact_in`'s actual output, measured as the balance diff over the swap sub-message,
+/// to the account's ledger.
+pub fn handle_swap_exact_in_reply(
+    mut deps: DepsMut,
+    env: Env,
+    reply: Reply,
+) -> Result<Response, ContractError> {
+    let SubMsgResult::Ok(_) = reply.result else {
+        return Err(ContractError::Std(StdError::generic_err("swap sub-message failed")));
+    };
+
+    let pending = PENDING_SWAP_EXACT_IN.load(deps.storage)?;
+    PENDING_SWAP_EXACT_IN.remove(deps.storage);
+
+    let balance_after =
+        balance::query_balance(deps.as_ref(), &env.contract.address, &pending.denom_out)?;
+    let amount_out =
+        balance_after.checked_sub(pending.balance_before).map_err(cosmwasm_std::StdError::overflow)?;
+
+    increment_coin_balance(deps.branch(), &pending.account_id, &Coin {
+        denom: pending.denom_out,
+        amount: amount_out,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "swap_exact_in_completed")
+        .add_attribute("amount_out", amount_out.to_string()))
+}
+
+/// Credit `swap_exact_out`'s actual output and refund any unspent `denom_in` reservation, both
+/// measured as balance diffs over the swap sub-message, to the account's ledger.
+pub fn handle_swap_exact_out_reply(
+    mut deps: DepsMut,
+    env: Env,
+    reply: Reply,
+) -> Result<Response, ContractError> {
+    let SubMsgResult::Ok(_) = reply.result else {
+        return Err(ContractError::Std(StdError::generic_err("swap sub-message failed")));
+    };
+
+    let pending = PENDING_SWAP_EXACT_OUT.load(deps.storage)?;
+    PENDING_SWAP_EXACT_OUT.remove(deps.storage);
+
+    let balance_after_out =
+        balance::query_balance(deps.as_ref(), &env.contract.address, &pending.denom_out)?;
+    let amount_out = balance_after_out
+        .checked_sub(pending.balance_before_out)
+        .map_err(cosmwasm_std::StdError::overflow)?;
+    increment_coin_balance(deps.branch(), &pending.account_id, &Coin {
+        denom: pending.denom_out,
+        amount: amount_out,
+    })?;
+
+    let balance_after_in =
+        balance::query_balance(deps.as_ref(), &env.contract.address, &pending.denom_in)?;
+    let amount_spent = pending
+        .balance_before_in
+        .checked_sub(balance_after_in)
+        .map_err(cosmwasm_std::StdError::overflow)?;
+    let refund = pending
+        .reserved_amount
+        .checked_sub(amount_spent)
+        .map_err(cosmwasm_std::StdError::overflow)?;
+    if !refund.is_zero() {
+        increment_coin_balance(deps.branch(), &pending.account_id, &Coin {
+            denom: pending.denom_in,
+            amount: refund,
+        })?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "swap_exact_out_completed")
+        .add_attribute("amount_out", amount_out.to_string())
+        .add_attribute("refund", refund.to_string()))
+}
@@ -1,10 +1,13 @@
 use std::str::FromStr;
 
-use cosmwasm_std::{coins, Addr, Coin, Decimal, OverflowError, OverflowOperation::Sub, Uint128};
-use mars_credit_manager::error::ContractError;
+use cosmwasm_std::{
+    coins, Addr, Coin, Decimal, IbcTimeout, OverflowError, OverflowOperation::Sub, Timestamp,
+    Uint128,
+};
+use mars_credit_manager::{error::ContractError, limiter::Limiter};
 use mars_swapper_mock::contract::MOCK_SWAP_RESULT;
 use mars_types::credit_manager::{
-    Action::{Deposit, SwapExactIn},
+    Action::{Deposit, SwapAndTransfer, SwapExactIn, SwapExactOut},
     ActionAmount, ActionCoin,
 };
 
@@ -119,6 +122,33 @@ fn user_has_zero_balance_for_swap_req() {
     )
 }
 
+#[test]
+fn full_balance_swap_is_no_op_when_balance_is_zero() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock =
+        MockEnv::new().set_params(&[osmo_info.clone(), atom_info.clone()]).build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![SwapExactIn {
+            coin_in: osmo_info.to_action_coin_full_balance(),
+            denom_out: atom_info.denom,
+            slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+        }],
+        &[],
+    )
+    .unwrap();
+
+    // no deposits were created; the swap was a no-op
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits.len(), 0);
+}
+
 #[test]
 fn slippage_too_high() {
     let osmo_info = uosmo_info();
@@ -154,6 +184,87 @@ fn slippage_too_high() {
     )
 }
 
+#[test]
+fn swap_exceeds_rate_limit() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let limit = Uint128::new(5_000);
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .swap_rate_limit(&osmo_info.denom, Limiter::Static(limit))
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(10_000, osmo_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(osmo_info.to_coin(10_000)),
+            SwapExactIn {
+                coin_in: osmo_info.to_action_coin(10_000),
+                denom_out: atom_info.denom,
+                slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+            },
+        ],
+        &coins(10_000, osmo_info.denom.clone()),
+    );
+
+    assert_err(
+        res,
+        ContractError::RateLimitExceeded {
+            denom: osmo_info.denom,
+            limit,
+            attempted: Uint128::new(10_000),
+        },
+    )
+}
+
+#[test]
+fn swap_blocked_when_denom_swapping_disabled() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .disable_swapping(&osmo_info.denom)
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(10_000, osmo_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(osmo_info.to_coin(10_000)),
+            SwapExactIn {
+                coin_in: osmo_info.to_action_coin(10_000),
+                denom_out: atom_info.denom,
+                slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+            },
+        ],
+        &coins(10_000, osmo_info.denom.clone()),
+    );
+
+    assert_err(
+        res,
+        ContractError::SwappingDisabled {
+            denom: osmo_info.denom,
+        },
+    )
+}
+
 #[test]
 fn user_does_not_have_enough_balance_for_swap_req() {
     let osmo_info = uosmo_info();
@@ -241,6 +352,145 @@ fn swap_success_with_specified_amount() {
     assert_eq!(position.deposits.first().unwrap().amount, MOCK_SWAP_RESULT);
 }
 
+#[test]
+fn swap_exact_out_denom_out_must_be_whitelisted() {
+    let blacklisted_coin = blacklisted_coin();
+    let osmo_info = uosmo_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().set_params(&[blacklisted_coin.clone()]).build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![SwapExactOut {
+            denom_in: osmo_info.denom,
+            coin_out: blacklisted_coin.to_action_coin(10_000),
+            slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+        }],
+        &[],
+    );
+
+    assert_err(res, ContractError::NotWhitelisted(blacklisted_coin.denom))
+}
+
+#[test]
+fn swap_exact_out_slippage_too_high() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let max_slippage = Decimal::percent(50);
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .max_slippage(max_slippage)
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let slippage = max_slippage + Decimal::from_str("0.000001").unwrap();
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![SwapExactOut {
+            denom_in: osmo_info.denom,
+            coin_out: atom_info.to_action_coin(10_000),
+            slippage,
+        }],
+        &[],
+    );
+
+    assert_err(
+        res,
+        ContractError::SlippageExceeded {
+            slippage,
+            max_slippage,
+        },
+    )
+}
+
+#[test]
+fn swap_exact_out_success() {
+    let atom_info = uatom_info();
+    let osmo_info = uosmo_info();
+
+    // the mock swapper's `EstimateExactOutSwap` always answers `MOCK_SWAP_RESULT`, so at 60%
+    // slippage the reserved spend is `MOCK_SWAP_RESULT / 0.4`; fund comfortably above that
+    let funded_atom = 18_000_000u128;
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![Coin::new(funded_atom, atom_info.denom.clone())],
+        })
+        .build()
+        .unwrap();
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom_info.to_coin(funded_atom)),
+            SwapExactOut {
+                denom_in: atom_info.denom.clone(),
+                coin_out: osmo_info.to_action_coin(MOCK_SWAP_RESULT.u128()),
+                slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+            },
+        ],
+        &[atom_info.to_coin(funded_atom)],
+    )
+    .unwrap();
+
+    // assert account received exactly the requested coin_out
+    let position = mock.query_positions(&account_id);
+    let osmo_position = position.deposits.iter().find(|c| c.denom == osmo_info.denom).unwrap();
+    assert_eq!(osmo_position.amount, MOCK_SWAP_RESULT);
+}
+
+#[test]
+fn swap_output_credited_via_smart_token_query_when_registered() {
+    let osmo_info = uosmo_info();
+    let atom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .enable_smart_tokens()
+        .register_smart_token(&osmo_info.denom, MOCK_SWAP_RESULT)
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![Coin::new(10_000u128, atom_info.denom.clone())],
+        })
+        .build()
+        .unwrap();
+
+    let account_id = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom_info.to_coin(10_000)),
+            SwapExactIn {
+                coin_in: atom_info.to_action_coin(10_000),
+                denom_out: osmo_info.denom.clone(),
+                slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+            },
+        ],
+        &[atom_info.to_coin(10_000)],
+    )
+    .unwrap();
+
+    // denom_out is registered as a smart token, so the post-swap amount credited to the account
+    // was read through the custom query rather than the standard bank query
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits.first().unwrap().denom, osmo_info.denom);
+    assert_eq!(position.deposits.first().unwrap().amount, MOCK_SWAP_RESULT);
+}
+
 #[test]
 fn swap_success_with_amount_none() {
     let atom_info = uatom_info();
@@ -287,3 +537,75 @@ fn swap_success_with_amount_none() {
     assert_eq!(position.deposits.first().unwrap().denom, osmo_info.denom);
     assert_eq!(position.deposits.first().unwrap().amount, MOCK_SWAP_RESULT);
 }
+
+#[test]
+fn swap_and_transfer_requires_allowed_channel() {
+    let atom_info = uatom_info();
+    let osmo_info = uosmo_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock =
+        MockEnv::new().set_params(&[osmo_info.clone(), atom_info.clone()]).build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![SwapAndTransfer {
+            coin_in: atom_info.to_action_coin(10_000),
+            denom_out: osmo_info.denom,
+            slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+            channel_id: "channel-0".to_string(),
+            to_address: "osmo1recipient".to_string(),
+            timeout: IbcTimeout::with_timestamp(Timestamp::from_seconds(1)),
+        }],
+        &[],
+    );
+
+    assert_err(
+        res,
+        ContractError::ChannelNotAllowed {
+            channel_id: "channel-0".to_string(),
+        },
+    )
+}
+
+#[test]
+fn swap_and_transfer_bridges_swap_output_over_ibc() {
+    let atom_info = uatom_info();
+    let osmo_info = uosmo_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[osmo_info.clone(), atom_info.clone()])
+        .allow_channel("channel-0")
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![Coin::new(10_000u128, atom_info.denom.clone())],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(atom_info.to_coin(10_000)),
+            SwapAndTransfer {
+                coin_in: atom_info.to_action_coin(10_000),
+                denom_out: osmo_info.denom.clone(),
+                slippage: Decimal::from_atomics(6u128, 1).unwrap(),
+                channel_id: "channel-0".to_string(),
+                to_address: "osmo1recipient".to_string(),
+                timeout: IbcTimeout::with_timestamp(Timestamp::from_seconds(1)),
+            },
+        ],
+        &[atom_info.to_coin(10_000)],
+    )
+    .unwrap();
+
+    // the swapped-out coins were bridged away over IBC, not kept in the account
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits.len(), 0);
+}
@@ -0,0 +1,465 @@
+use std::marker::PhantomData;
+
+use cosmwasm_std::{
+    Addr, BankMsg, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Order, Response,
+    StdResult, WasmMsg,
+};
+use cw_ownable::{assert_owner, initialize_owner, update_ownership, Action};
+use cw_storage_plus::{Bound, PrimaryKey};
+use mars_types::swapper::{
+    EstimateExactInSwapResponse, EstimateExactOutSwapResponse, ExecuteMsg, InstantiateMsg,
+    RouteResponse, RoutesResponse, SwapperRoute,
+};
+
+use crate::{error::ContractResult, state::routes, ContractError};
+
+/// Base logic shared by all chain-specific swapper contracts. Each chain implements
+/// `SwapperRoute` (e.g. `OsmosisRoute`) and type-aliases `SwapperBase<ItsRoute>` as its contract.
+pub struct SwapperBase<Route>(PhantomData<Route>);
+
+impl<Route: SwapperRoute> Default for SwapperBase<Route> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<Route: SwapperRoute> SwapperBase<Route> {
+    pub fn instantiate(
+        &self,
+        deps: DepsMut,
+        msg: InstantiateMsg,
+    ) -> ContractResult<Response> {
+        initialize_owner(deps.storage, deps.api, &msg.owner)?;
+        Ok(Response::new().add_attribute("action", "instantiate"))
+    }
+
+    pub fn update_owner(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        action: Action,
+    ) -> ContractResult<Response> {
+        Ok(update_ownership(deps, &info.sender.clone().into(), action)?)
+    }
+
+    pub fn set_route(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        denom_in: String,
+        denom_out: String,
+        route: Route,
+    ) -> ContractResult<Response> {
+        assert_owner(deps.storage, &info.sender)?;
+        route.validate(&deps.querier, &denom_in, &denom_out)?;
+        routes::<Route>().save(deps.storage, (&denom_in, &denom_out), &route)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_route")
+            .add_attribute("denom_in", denom_in)
+            .add_attribute("denom_out", denom_out))
+    }
+
+    pub fn swap_exact_in(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        coin_in: Coin,
+        denom_out: String,
+        slippage: Decimal,
+    ) -> ContractResult<Response> {
+        let route = self.query_route(deps.as_ref(), coin_in.denom.clone(), denom_out.clone())?;
+        let swap_msg =
+            route.route.build_exact_in_swap_msg(&deps.querier, &env, &coin_in, slippage)?;
+
+        // the native swap message sends proceeds to this contract's own balance, not the
+        // caller's; forward them on once the swap above has landed
+        let transfer_msg = self.transfer_result_msg(
+            &env,
+            info.sender,
+            coin_in.denom.clone(),
+            denom_out.clone(),
+        )?;
+
+        Ok(Response::new()
+            .add_message(swap_msg)
+            .add_message(transfer_msg)
+            .add_attribute("action", "swap_exact_in")
+            .add_attribute("coin_in", coin_in.to_string())
+            .add_attribute("denom_out", denom_out))
+    }
+
+    pub fn swap_exact_out(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        coin_in: Coin,
+        coin_out: Coin,
+        slippage: Decimal,
+    ) -> ContractResult<Response> {
+        let route =
+            self.query_route(deps.as_ref(), coin_in.denom.clone(), coin_out.denom.clone())?;
+        let swap_msg = route.route.build_exact_out_swap_msg(
+            &deps.querier,
+            &env,
+            &coin_in.denom,
+            &coin_out,
+            slippage,
+        )?;
+
+        // the native swap message sends both the swapped-out coin and any unspent `coin_in`
+        // change to this contract's own balance, not the caller's; forward them on once the swap
+        // above has landed
+        let transfer_msg = self.transfer_result_msg(
+            &env,
+            info.sender,
+            coin_in.denom.clone(),
+            coin_out.denom.clone(),
+        )?;
+
+        Ok(Response::new()
+            .add_message(swap_msg)
+            .add_message(transfer_msg)
+            .add_attribute("action", "swap_exact_out")
+            .add_attribute("denom_in", coin_in.denom)
+            .add_attribute("coin_out", coin_out.to_string()))
+    }
+
+    /// Build a self-call to `transfer_result`, queued to run immediately after a swap message in
+    /// the same response so it sees the swap's effect on this contract's own balance.
+    fn transfer_result_msg(
+        &self,
+        env: &Env,
+        recipient: Addr,
+        denom_in: String,
+        denom_out: String,
+    ) -> ContractResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: cosmwasm_std::to_json_binary(&ExecuteMsg::<Route>::TransferResult {
+                recipient,
+                denom_in,
+                denom_out,
+            })?,
+            funds: vec![],
+        }
+        .into())
+    }
+
+    /// Only callable by the contract itself, as the follow-up message `swap_exact_in`/
+    /// `swap_exact_out` chain after a swap to forward its proceeds on to the real caller.
+    pub fn transfer_result(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: Addr,
+        denom_in: String,
+        denom_out: String,
+    ) -> ContractResult<Response> {
+        if info.sender != env.contract.address {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        let in_balance = deps.querier.query_balance(&env.contract.address, &denom_in)?;
+        let out_balance = deps.querier.query_balance(&env.contract.address, &denom_out)?;
+
+        let mut funds = vec![];
+        if !out_balance.amount.is_zero() {
+            funds.push(out_balance);
+        }
+        if !in_balance.amount.is_zero() {
+            funds.push(in_balance);
+        }
+
+        let mut res = Response::new()
+            .add_attribute("action", "transfer_result")
+            .add_attribute("recipient", recipient.to_string());
+
+        if !funds.is_empty() {
+            res = res.add_message(CosmosMsg::Bank(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: funds,
+            }));
+        }
+
+        Ok(res)
+    }
+
+    pub fn query_route(
+        &self,
+        deps: Deps,
+        denom_in: String,
+        denom_out: String,
+    ) -> ContractResult<RouteResponse<Route>> {
+        let route = routes::<Route>()
+            .load(deps.storage, (&denom_in, &denom_out))
+            .map_err(|_| ContractError::RouteNotFound {
+                denom_in: denom_in.clone(),
+                denom_out: denom_out.clone(),
+            })?;
+        Ok(RouteResponse {
+            denom_in,
+            denom_out,
+            route,
+        })
+    }
+
+    pub fn query_routes(
+        &self,
+        deps: Deps,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    ) -> ContractResult<RoutesResponse<Route>> {
+        let start = start_after.map(|(denom_in, denom_out)| {
+            Bound::ExclusiveRaw((denom_in.as_str(), denom_out.as_str()).joined_key())
+        });
+        let routes = routes::<Route>()
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit.map_or(usize::MAX, |limit| limit as usize))
+            .map(|item| {
+                item.map(|((denom_in, denom_out), route)| RouteResponse {
+                    denom_in,
+                    denom_out,
+                    route,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(RoutesResponse {
+            routes,
+        })
+    }
+
+    pub fn estimate_exact_in_swap(
+        &self,
+        deps: Deps,
+        env: Env,
+        coin_in: Coin,
+        denom_out: String,
+    ) -> ContractResult<EstimateExactInSwapResponse> {
+        let route = self.query_route(deps, coin_in.denom.clone(), denom_out)?;
+        Ok(route.route.estimate_exact_in_swap(&deps.querier, &env, &coin_in)?)
+    }
+
+    pub fn estimate_exact_out_swap(
+        &self,
+        deps: Deps,
+        env: Env,
+        coin_out: Coin,
+        denom_in: String,
+    ) -> ContractResult<EstimateExactOutSwapResponse> {
+        let route = self.query_route(deps, denom_in.clone(), coin_out.denom.clone())?;
+        Ok(route.route.estimate_exact_out_swap(&deps.querier, &env, &denom_in, &coin_out)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env, mock_info, MOCK_CONTRACT_ADDR},
+        to_json_binary, QuerierWrapper, StdResult, Uint128,
+    };
+    use super::*;
+    use crate::state::routes;
+
+    /// A route that doesn't talk to any chain module; just enough to exercise `SwapperBase`
+    /// without a real swap backend.
+    #[cw_serde]
+    struct FakeRoute;
+
+    impl SwapperRoute for FakeRoute {
+        fn validate(
+            &self,
+            _querier: &QuerierWrapper,
+            _denom_in: &str,
+            _denom_out: &str,
+        ) -> StdResult<()> {
+            Ok(())
+        }
+
+        fn build_exact_in_swap_msg(
+            &self,
+            _querier: &QuerierWrapper,
+            _env: &Env,
+            coin_in: &Coin,
+            _slippage: Decimal,
+        ) -> StdResult<CosmosMsg> {
+            Ok(BankMsg::Burn {
+                amount: vec![coin_in.clone()],
+            }
+            .into())
+        }
+
+        fn build_exact_out_swap_msg(
+            &self,
+            _querier: &QuerierWrapper,
+            _env: &Env,
+            denom_in: &str,
+            coin_out: &Coin,
+            _slippage: Decimal,
+        ) -> StdResult<CosmosMsg> {
+            Ok(BankMsg::Burn {
+                amount: vec![Coin {
+                    denom: denom_in.to_string(),
+                    amount: coin_out.amount,
+                }],
+            }
+            .into())
+        }
+
+        fn estimate_exact_in_swap(
+            &self,
+            _querier: &QuerierWrapper,
+            _env: &Env,
+            coin_in: &Coin,
+        ) -> StdResult<EstimateExactInSwapResponse> {
+            Ok(EstimateExactInSwapResponse {
+                amount: coin_in.amount,
+            })
+        }
+
+        fn estimate_exact_out_swap(
+            &self,
+            _querier: &QuerierWrapper,
+            _env: &Env,
+            _denom_in: &str,
+            coin_out: &Coin,
+        ) -> StdResult<EstimateExactOutSwapResponse> {
+            Ok(EstimateExactOutSwapResponse {
+                amount: coin_out.amount,
+            })
+        }
+    }
+
+    /// Regression test for the swapper never forwarding a swap's proceeds: on a real chain, the
+    /// swap message lands funds in this contract's own balance, not the caller's, so
+    /// `swap_exact_in`/`swap_exact_out` must chain a follow-up `TransferResult` self-call rather
+    /// than assume the swap message itself pays the caller (as `mars-swapper-mock` conveniently
+    /// does in every other test that exercises a swap).
+    #[test]
+    fn swap_exact_in_chains_transfer_result_to_forward_proceeds() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("caller", &[]);
+        let swapper = SwapperBase::<FakeRoute>::default();
+
+        routes::<FakeRoute>()
+            .save(deps.as_mut().storage, ("uin", "uout"), &FakeRoute)
+            .unwrap();
+
+        let res = swapper
+            .swap_exact_in(
+                deps.as_mut(),
+                env.clone(),
+                info.clone(),
+                Coin {
+                    denom: "uin".to_string(),
+                    amount: Uint128::new(100),
+                },
+                "uout".to_string(),
+                Decimal::percent(1),
+            )
+            .unwrap();
+
+        assert_eq!(res.messages.len(), 2);
+        match &res.messages[1].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg,
+                funds,
+            }) => {
+                assert_eq!(contract_addr, env.contract.address.as_str());
+                assert!(funds.is_empty());
+                assert_eq!(
+                    msg,
+                    &to_json_binary(&ExecuteMsg::<FakeRoute>::TransferResult {
+                        recipient: info.sender,
+                        denom_in: "uin".to_string(),
+                        denom_out: "uout".to_string(),
+                    })
+                    .unwrap()
+                );
+            }
+            other => panic!("expected a self-call to TransferResult, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn transfer_result_rejects_callers_other_than_self() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let swapper = SwapperBase::<FakeRoute>::default();
+
+        let err = swapper
+            .transfer_result(
+                deps.as_mut(),
+                env,
+                mock_info("not_the_contract", &[]),
+                Addr::unchecked("recipient"),
+                "uin".to_string(),
+                "uout".to_string(),
+            )
+            .unwrap_err();
+
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+
+    #[test]
+    fn transfer_result_forwards_both_denoms_to_recipient() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let swapper = SwapperBase::<FakeRoute>::default();
+
+        deps.querier.update_balance(
+            MOCK_CONTRACT_ADDR,
+            vec![
+                Coin {
+                    denom: "uin".to_string(),
+                    amount: Uint128::new(5),
+                },
+                Coin {
+                    denom: "uout".to_string(),
+                    amount: Uint128::new(95),
+                },
+            ],
+        );
+
+        let res = swapper
+            .transfer_result(
+                deps.as_mut(),
+                env.clone(),
+                mock_info(env.contract.address.as_str(), &[]),
+                Addr::unchecked("recipient"),
+                "uin".to_string(),
+                "uout".to_string(),
+            )
+            .unwrap();
+
+        assert_eq!(res.messages.len(), 1);
+        match &res.messages[0].msg {
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address,
+                amount,
+            }) => {
+                assert_eq!(to_address, "recipient");
+                assert_eq!(
+                    amount,
+                    &vec![
+                        Coin {
+                            denom: "uout".to_string(),
+                            amount: Uint128::new(95),
+                        },
+                        Coin {
+                            denom: "uin".to_string(),
+                            amount: Uint128::new(5),
+                        },
+                    ]
+                );
+            }
+            other => panic!("expected a BankMsg::Send to the recipient, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use cosmwasm_std::StdError;
+use cw_ownable::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+
+    #[error("Route not found for denom_in {denom_in}, denom_out {denom_out}")]
+    RouteNotFound {
+        denom_in: String,
+        denom_out: String,
+    },
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+}
+
+pub type ContractResult<T> = Result<T, ContractError>;
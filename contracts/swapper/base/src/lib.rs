@@ -0,0 +1,6 @@
+pub mod contract;
+pub mod error;
+pub mod state;
+
+pub use contract::SwapperBase;
+pub use error::ContractError;
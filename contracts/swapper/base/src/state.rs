@@ -0,0 +1,7 @@
+use cw_storage_plus::Map;
+use mars_types::swapper::SwapperRoute;
+
+/// Swap routes indexed by (denom_in, denom_out)
+pub fn routes<Route: SwapperRoute>() -> Map<(&'static str, &'static str), Route> {
+    Map::new("routes")
+}
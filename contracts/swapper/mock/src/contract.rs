@@ -0,0 +1,74 @@
+use cosmwasm_std::{
+    coin, entry_point, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response,
+    StdResult, Uint128,
+};
+use mars_types::swapper::{
+    EstimateExactInSwapResponse, EstimateExactOutSwapResponse, ExecuteMsg, InstantiateMsg,
+    QueryMsg,
+};
+
+use cosmwasm_schema::cw_serde;
+
+/// Every swap in this mock returns exactly this amount of `denom_out`, regardless of the amount
+/// or denom of `coin_in`. Keeps assertions in integration tests simple.
+pub const MOCK_SWAP_RESULT: Uint128 = Uint128::new(7_142_857);
+
+#[cw_serde]
+pub struct MockRoute;
+
+#[entry_point]
+pub fn instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+#[entry_point]
+pub fn execute(
+    _deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg<MockRoute>,
+) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::SwapExactIn {
+            denom_out,
+            ..
+        }
+        | ExecuteMsg::SwapExactOut {
+            coin_out: Coin {
+                denom: denom_out, ..
+            },
+            ..
+        } => {
+            let send_msg: CosmosMsg = BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: vec![coin(MOCK_SWAP_RESULT.u128(), denom_out)],
+            }
+            .into();
+
+            Ok(Response::new().add_message(send_msg).add_attribute("action", "mock_swap"))
+        }
+        _ => Ok(Response::new()),
+    }
+}
+
+#[entry_point]
+pub fn query(_deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::EstimateExactInSwap {
+            ..
+        } => cosmwasm_std::to_json_binary(&EstimateExactInSwapResponse {
+            amount: MOCK_SWAP_RESULT,
+        }),
+        QueryMsg::EstimateExactOutSwap {
+            ..
+        } => cosmwasm_std::to_json_binary(&EstimateExactOutSwapResponse {
+            amount: MOCK_SWAP_RESULT,
+        }),
+        _ => cosmwasm_std::to_json_binary(&()),
+    }
+}
@@ -0,0 +1,82 @@
+use cosmwasm_std::{entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Response};
+use mars_swapper_base::{ContractResult, SwapperBase};
+use mars_types::swapper::{osmosis::OsmosisRoute, ExecuteMsg, InstantiateMsg, QueryMsg};
+
+/// The Osmosis swapper contract, generic `SwapperBase` specialized with `OsmosisRoute`
+pub type OsmosisSwapper = SwapperBase<OsmosisRoute>;
+
+pub const CONTRACT_NAME: &str = "crates.io:mars-swapper-osmosis";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[entry_point]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> ContractResult<Response> {
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    OsmosisSwapper::default().instantiate(deps, msg)
+}
+
+#[entry_point]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg<OsmosisRoute>,
+) -> ContractResult<Response> {
+    let swapper = OsmosisSwapper::default();
+    match msg {
+        ExecuteMsg::UpdateOwnership(action) => swapper.update_owner(deps, info, action),
+        ExecuteMsg::SetRoute {
+            denom_in,
+            denom_out,
+            route,
+        } => swapper.set_route(deps, info, denom_in, denom_out, route),
+        ExecuteMsg::SwapExactIn {
+            coin_in,
+            denom_out,
+            slippage,
+        } => swapper.swap_exact_in(deps, env, info, coin_in, denom_out, slippage),
+        ExecuteMsg::SwapExactOut {
+            coin_in,
+            coin_out,
+            slippage,
+        } => swapper.swap_exact_out(deps, env, info, coin_in, coin_out, slippage),
+        ExecuteMsg::TransferResult {
+            recipient,
+            denom_in,
+            denom_out,
+        } => swapper.transfer_result(deps, env, info, recipient, denom_in, denom_out),
+    }
+}
+
+#[entry_point]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
+    let swapper = OsmosisSwapper::default();
+    let res = match msg {
+        QueryMsg::Ownership {} => cosmwasm_std::to_json_binary(&cw_ownable::get_ownership(deps.storage)?)?,
+        QueryMsg::Route {
+            denom_in,
+            denom_out,
+        } => cosmwasm_std::to_json_binary(&swapper.query_route(deps, denom_in, denom_out)?)?,
+        QueryMsg::Routes {
+            start_after,
+            limit,
+        } => cosmwasm_std::to_json_binary(&swapper.query_routes(deps, start_after, limit)?)?,
+        QueryMsg::EstimateExactInSwap {
+            coin_in,
+            denom_out,
+        } => cosmwasm_std::to_json_binary(&swapper.estimate_exact_in_swap(
+            deps, env, coin_in, denom_out,
+        )?)?,
+        QueryMsg::EstimateExactOutSwap {
+            coin_out,
+            denom_in,
+        } => cosmwasm_std::to_json_binary(&swapper.estimate_exact_out_swap(
+            deps, env, coin_out, denom_in,
+        )?)?,
+    };
+    Ok(res)
+}
@@ -0,0 +1,173 @@
+use cosmwasm_std::{Coin, CosmosMsg, Decimal, Env, QuerierWrapper, StdError, StdResult, Uint128};
+use mars_types::swapper::{
+    osmosis::OsmosisRoute, EstimateExactInSwapResponse, EstimateExactOutSwapResponse, SwapperRoute,
+};
+use osmosis_std::types::{
+    cosmos::base::v1beta1::Coin as OsmosisCoin,
+    osmosis::poolmanager::v1beta1::{
+        MsgSwapExactAmountIn, MsgSwapExactAmountOut, PoolmanagerQuerier, SwapAmountInRoute,
+        SwapAmountOutRoute,
+    },
+};
+
+impl SwapperRoute for OsmosisRoute {
+    fn validate(
+        &self,
+        _querier: &QuerierWrapper,
+        _denom_in: &str,
+        denom_out: &str,
+    ) -> StdResult<()> {
+        let first = self.0.first().ok_or_else(|| StdError::generic_err("route must not be empty"))?;
+        if first.pool_id == 0 {
+            return Err(StdError::generic_err("route must not contain a pool id of zero"));
+        }
+
+        let last = self.0.last().unwrap();
+        if last.token_out_denom != denom_out {
+            return Err(StdError::generic_err(format!(
+                "the last step of the route must output {denom_out}"
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn build_exact_in_swap_msg(
+        &self,
+        querier: &QuerierWrapper,
+        env: &Env,
+        coin_in: &Coin,
+        slippage: Decimal,
+    ) -> StdResult<CosmosMsg> {
+        let estimate = estimate_exact_in_swap(self, querier, coin_in)?;
+        let token_out_min_amount = estimate.amount * (Decimal::one() - slippage);
+
+        Ok(MsgSwapExactAmountIn {
+            sender: env.contract.address.to_string(),
+            routes: self.0.iter().map(to_in_route).collect(),
+            token_in: Some(OsmosisCoin {
+                denom: coin_in.denom.clone(),
+                amount: coin_in.amount.to_string(),
+            }),
+            token_out_min_amount: token_out_min_amount.to_string(),
+        }
+        .into())
+    }
+
+    fn build_exact_out_swap_msg(
+        &self,
+        querier: &QuerierWrapper,
+        env: &Env,
+        denom_in: &str,
+        coin_out: &Coin,
+        slippage: Decimal,
+    ) -> StdResult<CosmosMsg> {
+        let estimate = estimate_exact_out_swap(self, querier, denom_in, coin_out)?;
+        // cap the spend at the estimated input for coin_out / (1 - slippage)
+        let token_in_max_amount = estimate.amount
+            * Decimal::one().checked_div(Decimal::one() - slippage).map_err(|e| {
+                StdError::generic_err(format!("failed to apply slippage: {e}"))
+            })?;
+
+        Ok(MsgSwapExactAmountOut {
+            sender: env.contract.address.to_string(),
+            routes: to_out_routes(&self.0, denom_in),
+            token_in_max_amount: token_in_max_amount.to_string(),
+            token_out: Some(OsmosisCoin {
+                denom: coin_out.denom.clone(),
+                amount: coin_out.amount.to_string(),
+            }),
+        }
+        .into())
+    }
+
+    fn estimate_exact_in_swap(
+        &self,
+        querier: &QuerierWrapper,
+        _env: &Env,
+        coin_in: &Coin,
+    ) -> StdResult<EstimateExactInSwapResponse> {
+        estimate_exact_in_swap(self, querier, coin_in)
+    }
+
+    fn estimate_exact_out_swap(
+        &self,
+        querier: &QuerierWrapper,
+        _env: &Env,
+        denom_in: &str,
+        coin_out: &Coin,
+    ) -> StdResult<EstimateExactOutSwapResponse> {
+        estimate_exact_out_swap(self, querier, denom_in, coin_out)
+    }
+}
+
+fn to_in_route(step: &SwapAmountInRoute) -> SwapAmountInRoute {
+    SwapAmountInRoute {
+        pool_id: step.pool_id,
+        token_out_denom: step.token_out_denom.clone(),
+    }
+}
+
+/// `MsgSwapExactAmountOut` routes are expressed in swap order; each hop's `token_in_denom` is
+/// what that hop consumes, starting with `denom_in` and ending at the pool that produces
+/// `coin_out`'s denom
+fn to_out_routes(steps: &[SwapAmountInRoute], denom_in: &str) -> Vec<SwapAmountOutRoute> {
+    let mut token_in_denom = denom_in.to_string();
+    steps
+        .iter()
+        .map(|step| {
+            let route = SwapAmountOutRoute {
+                pool_id: step.pool_id,
+                token_in_denom: token_in_denom.clone(),
+            };
+            token_in_denom = step.token_out_denom.clone();
+            route
+        })
+        .collect()
+}
+
+fn estimate_exact_in_swap(
+    route: &OsmosisRoute,
+    querier: &QuerierWrapper,
+    coin_in: &Coin,
+) -> StdResult<EstimateExactInSwapResponse> {
+    let first_pool_id =
+        route.0.first().ok_or_else(|| StdError::generic_err("route must not be empty"))?.pool_id;
+
+    let res = PoolmanagerQuerier::new(querier).estimate_swap_exact_amount_in(
+        first_pool_id,
+        coin_in.to_string(),
+        route.0.iter().map(to_in_route).collect(),
+    )?;
+
+    Ok(EstimateExactInSwapResponse {
+        amount: parse_amount(&res.token_out_amount)?,
+    })
+}
+
+fn estimate_exact_out_swap(
+    route: &OsmosisRoute,
+    querier: &QuerierWrapper,
+    denom_in: &str,
+    coin_out: &Coin,
+) -> StdResult<EstimateExactOutSwapResponse> {
+    let routes = to_out_routes(&route.0, denom_in);
+    let first_pool_id =
+        routes.first().ok_or_else(|| StdError::generic_err("route must not be empty"))?.pool_id;
+
+    let res = PoolmanagerQuerier::new(querier).estimate_swap_exact_amount_out(
+        first_pool_id,
+        routes,
+        coin_out.to_string(),
+    )?;
+
+    Ok(EstimateExactOutSwapResponse {
+        amount: parse_amount(&res.token_in_amount)?,
+    })
+}
+
+fn parse_amount(amount: &str) -> StdResult<Uint128> {
+    amount
+        .parse::<Uint128>()
+        .map_err(|_| StdError::generic_err(format!("failed to parse amount: {amount}")))
+}
@@ -0,0 +1,88 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, Decimal, IbcTimeout, Uint128};
+
+#[cw_serde]
+pub enum ActionAmount {
+    Exact(Uint128),
+    AccountBalance,
+}
+
+#[cw_serde]
+pub struct ActionCoin {
+    pub denom: String,
+    pub amount: ActionAmount,
+}
+
+#[cw_serde]
+pub enum Action {
+    Deposit(Coin),
+    Withdraw(ActionCoin),
+
+    /// Exchange exactly `coin_in` for the minimum amount of `denom_out` implied by `slippage`
+    SwapExactIn {
+        coin_in: ActionCoin,
+        denom_out: String,
+        slippage: Decimal,
+    },
+
+    /// Exchange the minimum amount of `denom_in` necessary to acquire exactly `coin_out`,
+    /// capping the spend at the estimated input for `coin_out`, divided by `(1 - slippage)`.
+    /// Any amount of `denom_in` reserved for
+    /// the swap but left unspent remains in the account.
+    SwapExactOut {
+        denom_in: String,
+        coin_out: ActionCoin,
+        slippage: Decimal,
+    },
+
+    /// Swap `coin_in` for `denom_out` exactly as `SwapExactIn` does, then bridge the resulting
+    /// `denom_out` coins out over IBC in the same call, so collateral can be swapped and
+    /// withdrawn cross-chain atomically
+    SwapAndTransfer {
+        coin_in: ActionCoin,
+        denom_out: String,
+        slippage: Decimal,
+        channel_id: String,
+        to_address: String,
+        timeout: IbcTimeout,
+    },
+}
+
+/// Powers available to the emergency owner, a privileged address that can act instantly to
+/// contain a threat (e.g. a depegged asset or a compromised pool), without waiting on a full
+/// governance cycle. Every power here must also be reachable through normal governance so the
+/// emergency owner is a fast-path, not a permanent override.
+#[cw_serde]
+pub enum CmEmergencyUpdate {
+    /// Block borrowing of `denom` across the protocol
+    DisableBorrowing(String),
+    /// Prevent `denom` from being used as new collateral
+    DisallowCoin(String),
+    /// Block `SwapExactIn`/`SwapExactOut` where `denom` is either the input or output denom
+    DisableSwapping(String),
+    /// Reverse `DisableSwapping`
+    EnableSwapping(String),
+}
+
+/// Points a denom at a contract exposing a custom balance query, for chain-native smart tokens
+/// (token-factory / module-issued denoms) whose balances aren't visible to the standard bank
+/// query
+#[cw_serde]
+pub struct SmartTokenBinding {
+    pub query_contract: Addr,
+}
+
+/// Query sent to a `SmartTokenBinding::query_contract` to resolve a holder's balance of the
+/// bound denom
+#[cw_serde]
+pub enum SmartTokenQueryMsg {
+    Balance {
+        address: String,
+        denom: String,
+    },
+}
+
+#[cw_serde]
+pub struct SmartTokenBalanceResponse {
+    pub amount: Uint128,
+}
@@ -0,0 +1,3 @@
+pub mod credit_manager;
+pub mod params;
+pub mod swapper;
@@ -0,0 +1,22 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+
+#[cw_serde]
+pub struct CmSettings {
+    /// Whether this denom may be used as collateral or swapped into/out of by credit accounts
+    pub whitelisted: bool,
+}
+
+#[cw_serde]
+pub struct AssetParams {
+    pub denom: String,
+    pub credit_manager: CmSettings,
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(Option<AssetParams>)]
+    AssetParams {
+        denom: String,
+    },
+}
@@ -0,0 +1,163 @@
+mod osmosis;
+
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Coin, CosmosMsg, Decimal, Env, QuerierWrapper, StdResult, Uint128};
+use cw_ownable::{cw_ownable_execute, cw_ownable_query};
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Serialize};
+
+pub use self::osmosis::OsmosisRoute;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub owner: String,
+}
+
+#[cw_ownable_execute]
+#[cw_serde]
+pub enum ExecuteMsg<Route> {
+    /// Configure the route for swapping an asset
+    ///
+    /// This is chain-specific, and can include parameters such as the slippage tolerance and the
+    /// routes for multi-step swaps
+    SetRoute {
+        denom_in: String,
+        denom_out: String,
+        route: Route,
+    },
+
+    /// Perform a swap, exchanging exactly `coin_in` for the minimum amount of `denom_out`
+    /// implied by `slippage`
+    SwapExactIn {
+        coin_in: Coin,
+        denom_out: String,
+        slippage: Decimal,
+    },
+
+    /// Perform a swap, exchanging the minimum amount of `coin_in` necessary to acquire exactly
+    /// `coin_out`, capping the spend at the estimated input for `coin_out`, divided by
+    /// `(1 - slippage)`
+    SwapExactOut {
+        coin_in: Coin,
+        coin_out: Coin,
+        slippage: Decimal,
+    },
+
+    /// Send swapped coin to recipient
+    TransferResult {
+        recipient: cosmwasm_std::Addr,
+        denom_in: String,
+        denom_out: String,
+    },
+}
+
+#[cw_ownable_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Return current route used to swap between denom_in and denom_out
+    #[returns(RouteResponse<cosmwasm_std::Empty>)]
+    Route {
+        denom_in: String,
+        denom_out: String,
+    },
+
+    /// Enumerate all swap routes
+    #[returns(RoutesResponse<cosmwasm_std::Empty>)]
+    Routes {
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    },
+
+    /// Return current estimate of output denom to receive if swapping exact amount of input denom
+    #[returns(EstimateExactInSwapResponse)]
+    EstimateExactInSwap {
+        coin_in: Coin,
+        denom_out: String,
+    },
+
+    /// Return the estimated amount of input denom required to receive exactly `coin_out`
+    #[returns(EstimateExactOutSwapResponse)]
+    EstimateExactOutSwap {
+        coin_out: Coin,
+        denom_in: String,
+    },
+}
+
+#[cw_serde]
+pub struct RouteResponse<Route> {
+    pub denom_in: String,
+    pub denom_out: String,
+    pub route: Route,
+}
+
+#[cw_serde]
+pub struct RoutesResponse<Route> {
+    pub routes: Vec<RouteResponse<Route>>,
+}
+
+#[cw_serde]
+pub struct EstimateExactInSwapResponse {
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct EstimateExactOutSwapResponse {
+    pub amount: Uint128,
+}
+
+/// Implemented by each chain-specific swap route (e.g. `OsmosisRoute`).
+///
+/// A `Route` tells `mars-swapper-base` how to build the messages and queries needed to execute a
+/// swap on the underlying DEX for a given chain.
+pub trait SwapperRoute:
+    Serialize + DeserializeOwned + Clone + std::fmt::Debug + PartialEq + JsonSchema
+{
+    /// Basic validation of the route, e.g. that it is non-empty and the first/last denoms match
+    /// `denom_in`/`denom_out`
+    fn validate(
+        &self,
+        querier: &QuerierWrapper,
+        denom_in: &str,
+        denom_out: &str,
+    ) -> StdResult<()>;
+
+    /// Build the message to swap exactly `coin_in` for the minimum amount of `denom_out` implied
+    /// by `slippage`
+    fn build_exact_in_swap_msg(
+        &self,
+        querier: &QuerierWrapper,
+        env: &Env,
+        coin_in: &Coin,
+        slippage: Decimal,
+    ) -> StdResult<CosmosMsg>;
+
+    /// Build the message to swap the minimum amount of `denom_in` necessary to acquire exactly
+    /// `coin_out`, capping the spend at the estimated input for `coin_out`, divided by
+    /// `(1 - slippage)`
+    fn build_exact_out_swap_msg(
+        &self,
+        querier: &QuerierWrapper,
+        env: &Env,
+        denom_in: &str,
+        coin_out: &Coin,
+        slippage: Decimal,
+    ) -> StdResult<CosmosMsg>;
+
+    /// Return current estimate of output denom to receive if swapping exact amount of input denom
+    fn estimate_exact_in_swap(
+        &self,
+        querier: &QuerierWrapper,
+        env: &Env,
+        coin_in: &Coin,
+    ) -> StdResult<EstimateExactInSwapResponse>;
+
+    /// Return the estimated amount of `denom_in` required to receive exactly `coin_out`
+    fn estimate_exact_out_swap(
+        &self,
+        querier: &QuerierWrapper,
+        env: &Env,
+        denom_in: &str,
+        coin_out: &Coin,
+    ) -> StdResult<EstimateExactOutSwapResponse>;
+}
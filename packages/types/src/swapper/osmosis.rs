@@ -0,0 +1,11 @@
+use cosmwasm_schema::cw_serde;
+use osmosis_std::types::osmosis::poolmanager::v1beta1::SwapAmountInRoute;
+
+/// An ordered list of pools through which a swap is to be routed on Osmosis.
+///
+/// `SwapAmountInRoute` is reused (rather than defining our own hop type) because it already has
+/// the shape we need (`pool_id`, `token_out_denom`) and it is what `MsgSwapExactAmountIn`
+/// expects. `MsgSwapExactAmountOut` takes `SwapAmountOutRoute` hops instead, which the swapper
+/// derives from these in the same (swap) order.
+#[cw_serde]
+pub struct OsmosisRoute(pub Vec<SwapAmountInRoute>);